@@ -11,23 +11,31 @@
 use std::{
     env,
     ffi::OsStr,
-    fs::DirBuilder,
+    fs::{self, DirBuilder},
     io,
     path::{Path, PathBuf},
-    process::{Command, Stdio},
 };
 use thiserror::Error;
 
 #[derive(Error, Debug)]
-pub(crate) enum InitializationError {
-    #[error("git is not installed")]
-    GitNotInstalled,
-    #[error("could not validate git installation; version check failed")]
-    GitValidation,
+pub enum InitializationError {
     #[error("io error searching for git directory")]
     RepositoryUnsearchable(#[from] io::Error),
     #[error("git directory not found")]
     RepositoryNotFound,
+    #[error("failed to open git repository: {0}")]
+    RepositoryOpenFailed(#[from] gix::open::Error),
+}
+
+/// Errors raised while GTI is marking, restoring, or reporting on temporarily ignored paths.
+#[derive(Error, Debug)]
+pub enum GtiError {
+    #[error("io error accessing GTI state")]
+    Io(#[from] io::Error),
+    #[error("failed to update git index: {0}")]
+    Index(String),
+    #[error("path is not tracked by git: {}", .0.display())]
+    PathNotTracked(PathBuf),
 }
 
 /// Fallback logger macros in the case that the default logger has failed or is not initialized.
@@ -43,19 +51,18 @@ macro_rules! fallback_log {
 }
 
 #[derive(Debug)]
-pub(crate) struct GtiManager {
-    #[allow(dead_code)]
+pub struct GtiManager {
+    repo: gix::Repository,
     gti_dir: PathBuf,
+    work_dir: Option<PathBuf>,
 }
 
 impl GtiManager {
     /// Build a GTI manager that contains a path to the `.git` directory.
-    pub fn new(repo_git_dir: &Path) -> io::Result<Self> {
+    pub fn new(repo_git_dir: &Path) -> Result<Self, InitializationError> {
+        let repo_git_dir = resolve_git_dir(repo_git_dir)?;
         if !repo_git_dir.exists() {
-            return Err(io::Error::new(
-                io::ErrorKind::NotFound,
-                ".git directory not found",
-            ));
+            return Err(InitializationError::RepositoryNotFound);
         }
 
         // .git directory exists, meaning that we can properly initialize .git.
@@ -64,44 +71,427 @@ impl GtiManager {
             DirBuilder::new().create(&gti_dir)?;
         }
 
-        Ok(GtiManager { gti_dir })
-    }
-}
-
-/// Validate the git installation. Check if git is installed and the version can be correctly obtained.
-pub(crate) fn git_validate_status() -> Result<PathBuf, InitializationError> {
-    let git_validate_status = Command::new("git")
-        .arg("--version")
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .status();
-
-    match git_validate_status {
-        Ok(pass) if !pass.success() => Err(InitializationError::GitValidation),
-        Err(_) => Err(InitializationError::GitNotInstalled),
-        _ => {
-            // git ran successfully -- search for git directory
-            let mut cur_dir = env::current_dir()?;
-            let look_for = OsStr::new(".git");
-            while cur_dir.parent().is_some() {
-                let repo_git_dir = cur_dir
-                    .read_dir()?
-                    .flatten()
-                    .map(|entry| entry.path())
-                    .find(|path| match path.file_name() {
-                        Some(s) => s == look_for,
-                        _ => false,
-                    });
-
-                if let Some(s) = repo_git_dir {
-                    return Ok(s);
-                }
-
-                // No .git found, pop cur_dir and go up a directory.
-                cur_dir.pop();
+        let trust = gix::sec::Trust::from_path_ownership(&repo_git_dir)?;
+        let repo = gix::open_opts(&repo_git_dir, open_options(trust))?;
+        let work_dir = effective_work_dir(&repo);
+
+        Ok(GtiManager {
+            repo,
+            gti_dir,
+            work_dir,
+        })
+    }
+
+    /// Mark `paths` as temporarily ignored: set the skip-worktree bit on their index entries and
+    /// record them in the manifest so the state survives across invocations. Each path must name
+    /// a tracked file; `apply_skip_worktree` rejects anything that doesn't resolve to an index
+    /// entry rather than silently recording it as hidden. A path whose bit is already set is not
+    /// added to the manifest.
+    pub fn ignore(&mut self, paths: &[PathBuf]) -> Result<(), GtiError> {
+        let relative = self.normalize_input_paths(paths)?;
+        let touched = self.apply_skip_worktree(&relative, true)?;
+
+        let mut ignored = self.read_manifest()?;
+        ignored.extend(touched);
+        ignored.sort();
+        ignored.dedup();
+        self.write_manifest(&ignored)
+    }
+
+    /// Restore `paths` that GTI is currently hiding: clear their skip-worktree bit and drop them
+    /// from the manifest. Paths GTI didn't mark itself are left untouched, so this never clobbers
+    /// a skip-worktree bit the user set by hand.
+    pub fn restore(&mut self, paths: &[PathBuf]) -> Result<(), GtiError> {
+        let mut ignored = self.read_manifest()?;
+        let to_restore: Vec<PathBuf> = self
+            .normalize_input_paths(paths)?
+            .into_iter()
+            .filter(|path| ignored.contains(path))
+            .collect();
+
+        self.apply_skip_worktree(&to_restore, false)?;
+
+        ignored.retain(|path| !to_restore.contains(path));
+        self.write_manifest(&ignored)
+    }
+
+    /// Restore every path GTI is currently hiding and clear the manifest.
+    pub fn restore_all(&mut self) -> Result<(), GtiError> {
+        // The manifest stores paths already in repo-relative form (see `apply_skip_worktree`),
+        // so they're applied as-is rather than routed back through `normalize_input_paths` --
+        // re-anchoring them to the current directory would break restores run from anywhere but
+        // the work-tree root.
+        let ignored = self.read_manifest()?;
+        self.apply_skip_worktree(&ignored, false)?;
+        self.write_manifest(&[])
+    }
+
+    /// List the paths GTI is currently hiding.
+    pub fn status(&self) -> Result<Vec<PathBuf>, GtiError> {
+        self.read_manifest()
+    }
+
+    /// Path to the manifest file recording which paths GTI currently has marked skip-worktree.
+    fn manifest_path(&self) -> PathBuf {
+        self.gti_dir.join("ignored")
+    }
+
+    fn read_manifest(&self) -> Result<Vec<PathBuf>, GtiError> {
+        let manifest_path = self.manifest_path();
+        if !manifest_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let contents = fs::read_to_string(manifest_path)?;
+        Ok(contents
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(PathBuf::from)
+            .collect())
+    }
+
+    fn write_manifest(&self, paths: &[PathBuf]) -> Result<(), GtiError> {
+        let contents = paths
+            .iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+            .join("\n");
+        fs::write(self.manifest_path(), contents)?;
+        Ok(())
+    }
+
+    /// Normalize raw, CWD-relative user input to the repo-relative form index entries are keyed
+    /// by. This must only be used for paths coming directly from a caller; paths already read
+    /// back out of the manifest are repo-relative already and would be corrupted by rebasing them
+    /// onto the current directory a second time (see `restore_all`).
+    fn normalize_input_paths(&self, paths: &[PathBuf]) -> io::Result<Vec<PathBuf>> {
+        paths
+            .iter()
+            .map(|path| relative_to_work_dir(path, self.work_dir.as_deref()))
+            .collect()
+    }
+
+    /// Flip the skip-worktree bit on index entries for `relative_paths`, which must already be in
+    /// the repo-relative form index entries are keyed by -- see `normalize_input_paths` for
+    /// turning raw user input into that form. A path that doesn't match any index entry is an
+    /// error rather than a silently-ignored no-op, since the caller relies on every path it
+    /// passes here to be truthfully reflected in the manifest afterward. Returns the paths whose
+    /// bit was actually flipped.
+    fn apply_skip_worktree(
+        &mut self,
+        relative_paths: &[PathBuf],
+        skip: bool,
+    ) -> Result<Vec<PathBuf>, GtiError> {
+        let mut index = self
+            .repo
+            .open_index()
+            .map_err(|e| GtiError::Index(e.to_string()))?;
+
+        let mut touched_indices = Vec::with_capacity(relative_paths.len());
+        for path in relative_paths {
+            let relative_str = path.to_string_lossy().into_owned();
+            let idx = index
+                .entries()
+                .iter()
+                .position(|entry| entry.path(&index.state).to_string() == relative_str)
+                .ok_or_else(|| GtiError::PathNotTracked(path.clone()))?;
+            touched_indices.push(idx);
+        }
+
+        let mut flipped = Vec::with_capacity(touched_indices.len());
+        for (i, path) in touched_indices.into_iter().zip(relative_paths) {
+            let entry = &mut index.entries_mut()[i];
+            if entry.flags.contains(gix::index::entry::Flags::SKIP_WORKTREE) == skip {
+                continue;
             }
+            if skip {
+                entry.flags.insert(gix::index::entry::Flags::SKIP_WORKTREE);
+            } else {
+                entry.flags.remove(gix::index::entry::Flags::SKIP_WORKTREE);
+            }
+            flipped.push(path.clone());
+        }
 
-            Err(InitializationError::RepositoryNotFound)
+        index
+            .write(gix::index::write::Options::default())
+            .map_err(|e| GtiError::Index(e.to_string()))?;
+        Ok(flipped)
+    }
+}
+
+/// Normalize `path` to the repo-relative form git index entries are keyed by: an absolute path is
+/// rebased onto `work_dir`, and any `.`/`..` components are collapsed lexically (the path may not
+/// exist on disk, e.g. one that's about to be restored).
+fn relative_to_work_dir(path: &Path, work_dir: Option<&Path>) -> io::Result<PathBuf> {
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        env::current_dir()?.join(path)
+    };
+
+    let mut normalized = PathBuf::new();
+    for component in absolute.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
         }
     }
+
+    Ok(match work_dir {
+        Some(work_dir) => normalized
+            .strip_prefix(work_dir)
+            .map(Path::to_path_buf)
+            .unwrap_or(normalized),
+        None => normalized,
+    })
+}
+
+/// Build `gix::open::Options` for the given trust level, as gitoxide expects when opening a
+/// repository that wasn't explicitly vetted by the user beforehand. A repository owned by
+/// another user on the machine is opened with `Reduced` trust: its local `.git/config` is still
+/// honored, but global/system config and includes are not, since those could otherwise be used
+/// to run arbitrary commands via e.g. a configured credential helper.
+fn open_options(trust: gix::sec::Trust) -> gix::open::Options {
+    use gix::open::permissions::Config;
+    use gix::sec::Trust;
+
+    let config = match trust {
+        Trust::Full => Config {
+            system: true,
+            git: true,
+            user: true,
+            env: true,
+            includes: true,
+        },
+        Trust::Reduced => Config {
+            system: false,
+            git: true,
+            user: false,
+            env: true,
+            includes: false,
+        },
+    };
+
+    gix::open::Options::default().permissions(gix::open::Permissions {
+        config,
+        ..gix::open::Permissions::default_for_level(trust)
+    })
+}
+
+/// Locate the enclosing repository starting from the current directory and open it, so later GTI operations can query its index and config directly
+/// instead of shelling out to `git`.
+pub fn git_validate_status() -> Result<PathBuf, InitializationError> {
+    discover_repo(&env::current_dir()?).map(|repo| repo.git_dir)
+}
+
+/// A single repository discovered from a target path: its resolved git directory, and its
+/// work-tree root when it has one (bare repositories don't).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DiscoveredRepo {
+    pub git_dir: PathBuf,
+    pub work_dir: Option<PathBuf>,
+}
+
+/// Run the full discovery pipeline -- environment overrides, the bounded upward walk, gitlink
+/// resolution, and the trust-aware `gix::open` -- starting from `start`.
+fn discover_repo(start: &Path) -> Result<DiscoveredRepo, InitializationError> {
+    let repo_git_dir = match git_dir_from_env()? {
+        Some(repo_git_dir) => repo_git_dir,
+        None => find_git_dir(start, &ceiling_dirs(&[]))?,
+    };
+    let repo_git_dir = resolve_git_dir(&repo_git_dir)?;
+    let trust = gix::sec::Trust::from_path_ownership(&repo_git_dir)?;
+
+    let repo = gix::open_opts(&repo_git_dir, open_options(trust))?;
+    Ok(DiscoveredRepo {
+        git_dir: repo.git_dir().to_path_buf(),
+        work_dir: effective_work_dir(&repo),
+    })
+}
+
+/// The work-tree root GTI should anchor its operations to: `GIT_WORK_TREE` when set, since it
+/// names the work tree explicitly and independently of however `GIT_DIR` was resolved, and
+/// otherwise whatever `gix` determined when opening the repository.
+fn effective_work_dir(repo: &gix::Repository) -> Option<PathBuf> {
+    env::var_os("GIT_WORK_TREE")
+        .map(PathBuf::from)
+        .or_else(|| repo.work_dir().map(Path::to_path_buf))
+}
+
+/// Resolve the `GIT_DIR`/`GIT_WORK_TREE` environment overrides, mirroring how `git` itself roots
+/// these two variables relative to one another. When `GIT_DIR` is set, it short-circuits the
+/// upward directory search entirely -- this is what lets GTI run correctly inside hooks and build
+/// scripts that already export it.
+fn git_dir_from_env() -> io::Result<Option<PathBuf>> {
+    let Some(git_dir) = env::var_os("GIT_DIR") else {
+        return Ok(None);
+    };
+    let git_dir = PathBuf::from(git_dir);
+    if git_dir.is_absolute() {
+        return Ok(Some(git_dir));
+    }
+
+    // A relative GIT_DIR is resolved against GIT_WORK_TREE when present, and the current
+    // directory otherwise, matching git's own resolution rules.
+    let base = match env::var_os("GIT_WORK_TREE") {
+        Some(work_tree) => PathBuf::from(work_tree),
+        None => env::current_dir()?,
+    };
+    Ok(Some(base.join(git_dir)))
+}
+
+/// Collect the ceiling directories that bound the upward `.git` search: those listed in the
+/// colon-separated `GIT_CEILING_DIRECTORIES` environment variable, plus any caller-supplied
+/// `extra` ceilings, all canonicalized so the comparison in `find_git_dir` is robust against
+/// symlinks and relative paths.
+fn ceiling_dirs(extra: &[PathBuf]) -> Vec<PathBuf> {
+    let from_env = env::var_os("GIT_CEILING_DIRECTORIES")
+        .map(|val| env::split_paths(&val).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    from_env
+        .iter()
+        .chain(extra)
+        .filter_map(|dir| dir.canonicalize().ok())
+        .collect()
+}
+
+/// Walk upward from `start` looking for a `.git` entry, returning the entry itself (which may be
+/// a directory or, for linked worktrees and submodules, a file) for `resolve_git_dir` to follow.
+/// The search never ascends past a directory listed in `ceilings`.
+fn find_git_dir(start: &Path, ceilings: &[PathBuf]) -> Result<PathBuf, InitializationError> {
+    let mut cur_dir = start.to_path_buf();
+    let look_for = OsStr::new(".git");
+    let mut first = true;
+    while cur_dir.parent().is_some() {
+        // The starting directory itself is always searched, even if it's listed as a ceiling --
+        // git never excludes the directory the search started from, only the ones it would have
+        // to ascend into afterward.
+        if !first {
+            let canonical_cur_dir = cur_dir.canonicalize().unwrap_or_else(|_| cur_dir.clone());
+            if ceilings.contains(&canonical_cur_dir) {
+                break;
+            }
+        }
+        first = false;
+
+        let found = cur_dir
+            .read_dir()?
+            .flatten()
+            .map(|entry| entry.path())
+            .find(|path| matches!(path.file_name(), Some(s) if s == look_for));
+
+        if let Some(path) = found {
+            return Ok(path);
+        }
+
+        // No .git found, pop cur_dir and go up a directory.
+        cur_dir.pop();
+    }
+
+    Err(InitializationError::RepositoryNotFound)
+}
+
+/// Resolve a `.git` entry to the real git directory it designates. A linked worktree or
+/// submodule stores a `.git` *file* containing a `gitdir: <path>` line rather than a directory;
+/// this follows that link and, when the target turns out to be a worktree's private admin
+/// directory (`.../worktrees/<name>`), follows its `commondir` file one step further. That's
+/// where the *shared* git state lives, which is what `x-gti-info` should be anchored to so
+/// ignore state is shared across worktrees of the same repository rather than duplicated.
+fn resolve_git_dir(entry: &Path) -> io::Result<PathBuf> {
+    if !entry.is_file() {
+        return Ok(entry.to_path_buf());
+    }
+
+    let contents = fs::read_to_string(entry)?;
+    let gitdir_line = contents
+        .lines()
+        .find_map(|line| line.strip_prefix("gitdir:"))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "malformed .git file"))?
+        .trim();
+
+    let parent = entry.parent().unwrap_or_else(|| Path::new("."));
+    let linked_dir = parent.join(gitdir_line);
+    let linked_dir = linked_dir.canonicalize().unwrap_or(linked_dir);
+
+    let commondir_file = linked_dir.join("commondir");
+    if commondir_file.is_file() {
+        let commondir = fs::read_to_string(&commondir_file)?;
+        let common_dir = linked_dir.join(commondir.trim());
+        return Ok(common_dir.canonicalize().unwrap_or(common_dir));
+    }
+
+    Ok(linked_dir)
+}
+
+/// Caches repositories discovered while GTI operates on several target paths in one run, so the
+/// (potentially expensive) upward walk only ever happens once per distinct repository. Paths that
+/// turned out not to be in any repository are remembered too, so re-probing a known dead end is
+/// also free.
+#[derive(Debug, Default)]
+pub(crate) struct GitCache {
+    repos: Vec<DiscoveredRepo>,
+    misses: Vec<PathBuf>,
+}
+
+impl GitCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolve `path` to its enclosing repository, reusing a cached entry when `path` already
+    /// falls under a known repo's work tree (or git dir, for bare repos) or a previously
+    /// confirmed miss. Repositories are deduplicated by their resolved work-tree so two
+    /// subdirectories of the same repo share one handle.
+    pub fn resolve(&mut self, path: &Path) -> Result<&DiscoveredRepo, InitializationError> {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+
+        // Discovery walks *upward*, so a miss recorded for some directory only tells us that
+        // directory's own upward walk found nothing -- it says nothing about a path *beneath*
+        // it, whose walk inspects directories the miss's walk never saw. A cached miss can only
+        // ever be reused for an ancestor of the path that produced it (or the exact path itself).
+        if self.misses.iter().any(|miss| miss.starts_with(&path)) {
+            return Err(InitializationError::RepositoryNotFound);
+        }
+
+        if let Some(idx) = self.repos.iter().position(|repo| repo.contains(&path)) {
+            return Ok(&self.repos[idx]);
+        }
+
+        match discover_repo(&path) {
+            Ok(repo) => {
+                let idx = match self.repos.iter().position(|existing| existing.same_repo(&repo)) {
+                    Some(idx) => idx,
+                    None => {
+                        self.repos.push(repo);
+                        self.repos.len() - 1
+                    }
+                };
+                Ok(&self.repos[idx])
+            }
+            Err(e) => {
+                self.misses.push(path);
+                Err(e)
+            }
+        }
+    }
+}
+
+impl DiscoveredRepo {
+    /// The directory GTI roots repository-specific operations at: the work tree for a normal
+    /// repo, or the git dir itself for a bare one.
+    fn root(&self) -> &Path {
+        self.work_dir.as_deref().unwrap_or(&self.git_dir)
+    }
+
+    fn contains(&self, path: &Path) -> bool {
+        path.starts_with(self.root())
+    }
+
+    fn same_repo(&self, other: &DiscoveredRepo) -> bool {
+        self.root() == other.root()
+    }
 }
@@ -1,6 +1,6 @@
-//! # Temp Ignore Git (TIG)
+//! # Git Temp Ignore (GTI)
 //!
-//! TIG is a wrapper around git that streamlines temporarily ignoring files changes when
+//! GTI is a wrapper around git that streamlines temporarily ignoring files changes when
 //! running commands. It's particularly useful if a build script, outside of your control,
 //! makes minor changes to a repository that does not need to be tracked.
 //!
@@ -8,21 +8,36 @@
 //! re-running scripts when relevant major changes have already been made but not yet
 //! committed.
 
-mod tig;
+mod gti;
 
-use std::{io, process::exit};
-use tig::TigManager;
+use gti::{GitCache, GtiManager};
+use std::{env, io, path::PathBuf, process::exit};
 
 fn main() -> io::Result<()> {
-    let repo_git_dir = match tig::git_validate_status() {
-        Ok(path) => path,
-        Err(e) => {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let targets: Vec<PathBuf> = if args.is_empty() {
+        vec![env::current_dir()?]
+    } else {
+        args.into_iter().map(PathBuf::from).collect()
+    };
+
+    // GitCache dedups repeated targets under the same repository so its (potentially expensive)
+    // discovery walk only runs once per distinct repo, even when several targets are passed.
+    let mut cache = GitCache::new();
+    for target in &targets {
+        let git_dir = match cache.resolve(target) {
+            Ok(repo) => repo.git_dir.clone(),
+            Err(e) => {
+                fallback_log!(e);
+                exit(1);
+            }
+        };
+
+        if let Err(e) = GtiManager::new(&git_dir) {
             fallback_log!(e);
             exit(1);
         }
-    };
-
-    let _tig = TigManager::new(&repo_git_dir)?;
+    }
 
     Ok(())
 }
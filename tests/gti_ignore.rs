@@ -0,0 +1,105 @@
+mod common;
+
+use common::{repo_git_dir, TEST_ENV_PATH};
+use gti::GtiManager;
+use std::{
+    fs,
+    io,
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+/// Write `relative` to the test environment and commit it, so it lands in the index as a
+/// tracked file GTI can mark skip-worktree.
+fn commit_tracked_file(relative: &str) -> io::Result<()> {
+    fs::write(TEST_ENV_PATH.join(relative), "gti test fixture\n")?;
+
+    Command::new("git")
+        .args(["add", relative])
+        .stdout(Stdio::null())
+        .status()?;
+    Command::new("git")
+        .args([
+            "-c",
+            "user.email=gti@example.com",
+            "-c",
+            "user.name=gti",
+            "commit",
+            "-m",
+            "gti fixture",
+        ])
+        .stdout(Stdio::null())
+        .status()?;
+    Ok(())
+}
+
+/// Whether `git` currently reports the skip-worktree bit set on `relative`. Always queried from
+/// the work-tree root via `-C`, so the result doesn't depend on the test's current directory.
+fn is_skip_worktree(relative: &str) -> io::Result<bool> {
+    let output = Command::new("git")
+        .args(["-C", &TEST_ENV_PATH.to_string_lossy(), "ls-files", "-v", relative])
+        .output()?;
+    Ok(String::from_utf8_lossy(&output.stdout).starts_with('S'))
+}
+
+#[test]
+fn ignore_sets_skip_worktree_and_records_manifest() -> io::Result<()> {
+    common::setup()?;
+    commit_tracked_file("tracked.txt")?;
+
+    let git_dir = &repo_git_dir();
+    let gti = GtiManager::new(git_dir);
+    assert!(gti.is_ok());
+    let mut gti = gti.unwrap();
+
+    assert!(gti.ignore(&[PathBuf::from("tracked.txt")]).is_ok());
+    assert!(is_skip_worktree("tracked.txt")?);
+    assert_eq!(gti.status().unwrap(), vec![PathBuf::from("tracked.txt")]);
+
+    common::cleanup()?;
+    Ok(())
+}
+
+#[test]
+fn restore_all_clears_skip_worktree_from_work_tree_root() -> io::Result<()> {
+    common::setup()?;
+    commit_tracked_file("tracked.txt")?;
+
+    let git_dir = &repo_git_dir();
+    let gti = GtiManager::new(git_dir);
+    assert!(gti.is_ok());
+    let mut gti = gti.unwrap();
+
+    assert!(gti.ignore(&[PathBuf::from("tracked.txt")]).is_ok());
+    assert!(gti.restore_all().is_ok());
+
+    assert!(!is_skip_worktree("tracked.txt")?);
+    assert!(gti.status().unwrap().is_empty());
+
+    common::cleanup()?;
+    Ok(())
+}
+
+#[test]
+fn restore_all_clears_skip_worktree_from_a_subdirectory() -> io::Result<()> {
+    common::setup()?;
+    fs::create_dir_all(TEST_ENV_PATH.join("sub"))?;
+    commit_tracked_file("tracked.txt")?;
+
+    let git_dir = &repo_git_dir();
+    let gti = GtiManager::new(git_dir);
+    assert!(gti.is_ok());
+    let mut gti = gti.unwrap();
+
+    assert!(gti.ignore(&[PathBuf::from("tracked.txt")]).is_ok());
+
+    std::env::set_current_dir(TEST_ENV_PATH.join("sub"))?;
+    assert!(gti.restore_all().is_ok());
+
+    assert!(!is_skip_worktree("tracked.txt")?);
+    assert!(gti.status().unwrap().is_empty());
+
+    std::env::set_current_dir(&*TEST_ENV_PATH)?;
+    common::cleanup()?;
+    Ok(())
+}
@@ -1,18 +1,18 @@
 mod common;
 
 use common::repo_git_dir;
+use gti::GtiManager;
 use std::io;
-use tig::TigManager;
 
 #[test]
-fn tig_init_folder_created() -> io::Result<()> {
+fn gti_init_folder_created() -> io::Result<()> {
     common::setup()?;
 
     let git_dir = &repo_git_dir();
-    let tig = TigManager::new(git_dir);
+    let gti = GtiManager::new(git_dir);
 
-    assert!(tig.is_ok());
-    assert!(git_dir.join("x-tig-info").exists());
+    assert!(gti.is_ok());
+    assert!(git_dir.join("x-gti-info").exists());
 
     common::cleanup()?;
     Ok(())